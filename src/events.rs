@@ -1,5 +1,11 @@
-use alloc::{boxed::Box, collections::BTreeMap, vec::Vec};
-use core::any::Any;
+use crate::sync::SpinMutex;
+use alloc::{boxed::Box, collections::BTreeMap, sync::Arc, vec::Vec};
+use core::{
+    any::Any,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll, Waker},
+};
 
 /// A type-erased event that can hold any data
 pub struct Event {
@@ -54,16 +60,21 @@ pub struct EventListener<T> {
 pub struct EventBus {
     /// A map of event type IDs to a list of listeners
     listeners: BTreeMap<u64, Vec<Box<dyn Any + Send>>>,
+    /// A map of event type IDs to the one-shot slots registered by
+    /// [`EventBus::next`], shared so a [`NextEvent`] can remove its own
+    /// slot on drop without holding `&EventBus`
+    waiters: Arc<SpinMutex<BTreeMap<u64, Vec<Box<dyn Any + Send>>>>>,
 }
 
 impl EventBus {
     /// Creates a new empty event bus
-    /// 
+    ///
     /// # Returns
     /// A new event bus with no listeners
     pub fn new() -> Self {
         Self {
             listeners: BTreeMap::new(),
+            waiters: Arc::new(SpinMutex::new(BTreeMap::new())),
         }
     }
 
@@ -82,25 +93,71 @@ impl EventBus {
             .push(Box::new(listener));
     }
 
-    /// Emit an event to all registered listeners
-    /// 
+    /// Emit an event to all registered listeners and wake any task awaiting
+    /// [`EventBus::next`] for this event type.
+    ///
     /// # Arguments
     /// * `event` - The event to be emitted
-    /// 
+    ///
     /// # Type Parameters
     /// * `T` - The type of the event being emitted
-    pub fn emit<T: 'static + Send>(&self, event: T) {
-        let event = Event::new(event);
-        let type_id = event.type_id();
+    pub fn emit<T: 'static + Send + Clone>(&self, event: T) {
+        let boxed_event = Event::new(event.clone());
+        let type_id = boxed_event.type_id();
         if let Some(listeners) = self.listeners.get(&type_id) {
             for listener in listeners {
                 if let Some(listener) = listener.downcast_ref::<EventListener<T>>() {
-                    if let Some(data) = event.get_data::<T>() {
+                    if let Some(data) = boxed_event.get_data::<T>() {
                         (listener.callback)(data);
                     }
                 }
             }
         }
+        self.wake_waiters(type_id, event);
+    }
+
+    /// Fills every pending [`NextEvent`] slot for `type_id` with a clone of
+    /// `event` and wakes its stored waker, if any.
+    fn wake_waiters<T: 'static + Send + Clone>(&self, type_id: u64, event: T) {
+        let slots = self.waiters.with(|waiters| waiters.remove(&type_id));
+        let Some(slots) = slots else { return };
+        for slot in slots {
+            if let Ok(slot) = slot.downcast::<Arc<EventSlot<T>>>() {
+                slot.value.with(|value| *value = Some(event.clone()));
+                if let Some(waker) = slot.waker.with(|slot| slot.take()) {
+                    waker.wake();
+                }
+            }
+        }
+    }
+
+    /// Returns a future that resolves to the next event of type `T` emitted
+    /// on this bus.
+    ///
+    /// Unlike `subscribe`, this lets a spawned task wait on an event
+    /// directly: `let ev = bus.next::<KeyEvent>().await;`. Registers a
+    /// one-shot slot keyed by `T`'s type ID; [`EventBus::emit`] fills it
+    /// with a clone of the event and wakes the awaiting task.
+    ///
+    /// # Type Parameters
+    /// * `T` - The type of event to wait for
+    pub fn next<T: 'static + Send>(&self) -> NextEvent<T> {
+        let type_id = Self::get_type_id::<T>();
+        let slot = Arc::new(EventSlot {
+            value: SpinMutex::new(None),
+            waker: SpinMutex::new(None),
+        });
+        self.waiters.with(|waiters| {
+            waiters
+                .entry(type_id)
+                .or_insert_with(Vec::new)
+                .push(Box::new(slot.clone()));
+        });
+        NextEvent {
+            type_id,
+            slot,
+            waiters: self.waiters.clone(),
+        }
     }
 
     /// Generate a unique ID for a type
@@ -136,12 +193,111 @@ impl<T> EventListener<T> {
     /// 
     /// # Constraints
     /// * `F` must be a function that takes a reference to type `T` and returns `()`
-    pub fn new<F>(callback: F) -> Self 
+    pub fn new<F>(callback: F) -> Self
     where
-        F: Fn(&T) + Send + 'static 
+        F: Fn(&T) + Send + 'static
     {
         Self {
             callback: Box::new(callback),
         }
     }
+}
+
+/// State shared between a [`NextEvent`] and the [`EventBus`] it was
+/// registered on.
+struct EventSlot<T> {
+    /// The event's value, once `emit` has filled it
+    value: SpinMutex<Option<T>>,
+    /// Waker registered by a pending `NextEvent::poll`, if any
+    waker: SpinMutex<Option<Waker>>,
+}
+
+/// A future returned by [`EventBus::next`], resolving to the next event of
+/// type `T` emitted on the bus it was created from.
+///
+/// If dropped before an event arrives, its slot is removed from the bus's
+/// waiters so a later `emit` doesn't keep it around forever.
+pub struct NextEvent<T: 'static + Send> {
+    /// Type ID this slot is registered under in the bus's waiters map
+    type_id: u64,
+    slot: Arc<EventSlot<T>>,
+    /// The bus's waiters map, kept alive so `drop` can remove `slot`
+    waiters: Arc<SpinMutex<BTreeMap<u64, Vec<Box<dyn Any + Send>>>>>,
+}
+
+impl<T: 'static + Send> Future for NextEvent<T> {
+    type Output = T;
+
+    fn poll(self: Pin<&mut Self>, context: &mut Context) -> Poll<T> {
+        // Register the waker before checking the value slot: otherwise
+        // `emit` could fill the slot and find no waker to call in the
+        // window between our check and our registration, leaving the
+        // waker we register afterwards never invoked.
+        self.slot
+            .waker
+            .with(|slot| *slot = Some(context.waker().clone()));
+        if let Some(value) = self.slot.value.with(|slot| slot.take()) {
+            return Poll::Ready(value);
+        }
+        Poll::Pending
+    }
+}
+
+impl<T: 'static + Send> Drop for NextEvent<T> {
+    fn drop(&mut self) {
+        self.waiters.with(|waiters| {
+            if let Some(slots) = waiters.get_mut(&self.type_id) {
+                slots.retain(|slot| match slot.downcast_ref::<Arc<EventSlot<T>>>() {
+                    Some(existing) => !Arc::ptr_eq(existing, &self.slot),
+                    None => true,
+                });
+                if slots.is_empty() {
+                    waiters.remove(&self.type_id);
+                }
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simple_executor::dummy_waker;
+    use core::pin::Pin;
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Ping(u32);
+
+    #[test]
+    fn next_resolves_once_the_matching_event_is_emitted() {
+        let bus = EventBus::new();
+        let mut next = bus.next::<Ping>();
+
+        let waker = dummy_waker();
+        let mut context = Context::from_waker(&waker);
+        assert_eq!(Pin::new(&mut next).poll(&mut context), Poll::Pending);
+
+        bus.emit(Ping(42));
+
+        assert_eq!(Pin::new(&mut next).poll(&mut context), Poll::Ready(Ping(42)));
+    }
+
+    #[test]
+    fn dropping_a_next_event_before_emit_removes_its_waiter_slot() {
+        let bus = EventBus::new();
+        let type_id = EventBus::get_type_id::<Ping>();
+
+        {
+            let mut next = bus.next::<Ping>();
+            let waker = dummy_waker();
+            let mut context = Context::from_waker(&waker);
+            assert_eq!(Pin::new(&mut next).poll(&mut context), Poll::Pending);
+            assert!(bus.waiters.with(|waiters| waiters.contains_key(&type_id)));
+        } // `next` dropped here, well before `emit`
+
+        assert!(!bus.waiters.with(|waiters| waiters.contains_key(&type_id)));
+
+        // A later emit with no waiters left must not panic.
+        bus.emit(Ping(7));
+    }
 }
\ No newline at end of file
@@ -1,66 +1,210 @@
-use crate::task::{Task, TaskId};
-use alloc::{collections::BTreeMap, sync::Arc, task::Wake};
-use core::task::{Context, Poll, Waker};
-use crossbeam_queue::ArrayQueue;
+#[cfg(feature = "metrics")]
+use crate::metrics::{Metrics, MetricsSnapshot};
+use crate::sync::SpinMutex;
+use crate::task::{CancelSink, JoinHandle, Task, TaskId};
+use crate::timer::{Clock, Timer, TimerHandle, TimerHeap};
+use alloc::{collections::BTreeMap, sync::Arc, task::Wake, vec::Vec};
+use core::{
+    future::Future,
+    mem,
+    task::{Context, Poll, Waker},
+};
+use crossbeam_queue::SegQueue;
 
 /// An async task executor that manages multiple tasks and their wakers.
-/// Uses a combination of BTreeMap for task storage and ArrayQueue for scheduling.
+/// Uses a combination of BTreeMap for task storage and an unbounded SegQueue for scheduling.
 pub struct Executor {
     /// Stores all tasks indexed by their TaskId
     tasks: BTreeMap<TaskId, Task>,
-    /// Queue of task IDs that are ready to be polled
-    task_queue: Arc<ArrayQueue<TaskId>>,
+    /// Queue of task IDs that are ready to be polled. Unbounded, so pushing
+    /// a task ID can never fail no matter how many tasks or wakeups are in flight.
+    task_queue: Arc<SegQueue<TaskId>>,
     /// Cache of wakers for each task to avoid recreation
     waker_cache: BTreeMap<TaskId, Waker>,
+    /// Heap of pending timers, shared with every outstanding `Timer` future
+    timers: TimerHandle,
+    /// Caller-supplied tick source backing `Timer` deadlines
+    clock: Arc<dyn Clock>,
+    /// Task IDs whose `JoinHandle` was dropped and which should be
+    /// cancelled at the next safe point (the start of `run_ready_tasks`)
+    pending_cancellations: Arc<SpinMutex<Vec<TaskId>>>,
+    /// Spawn/poll/completion/wakeup/idle counters, present only when the
+    /// `metrics` feature is enabled
+    #[cfg(feature = "metrics")]
+    metrics: Arc<Metrics>,
+}
+
+impl CancelSink for SpinMutex<Vec<TaskId>> {
+    fn request_cancel(&self, id: TaskId) {
+        self.with(|pending| pending.push(id));
+    }
 }
 
 impl Executor {
-    /// Creates a new Executor with empty task collections and a fixed-size queue.
-    pub fn new() -> Self {
+    /// Creates a new Executor with empty task collections and an unbounded ready queue.
+    ///
+    /// # Arguments
+    /// * `clock` - Tick source used to resolve timer deadlines
+    pub fn new(clock: impl Clock + 'static) -> Self {
         Executor {
             tasks: BTreeMap::new(),
-            task_queue: Arc::new(ArrayQueue::new(100)),
+            task_queue: Arc::new(SegQueue::new()),
             waker_cache: BTreeMap::new(),
+            timers: Arc::new(SpinMutex::new(TimerHeap::new())),
+            clock: Arc::new(clock),
+            pending_cancellations: Arc::new(SpinMutex::new(Vec::new())),
+            #[cfg(feature = "metrics")]
+            metrics: Arc::new(Metrics::new()),
         }
     }
 
-    /// Spawns a new task into the executor.
-    /// 
+    /// Returns a copy of this executor's instrumentation counters. Each
+    /// counter is read independently (see [`Metrics::snapshot`]), so the
+    /// copy is not guaranteed to be consistent across counters while
+    /// wakers fire concurrently. Only available with the `metrics`
+    /// feature enabled.
+    #[cfg(feature = "metrics")]
+    pub fn metrics(&self) -> MetricsSnapshot {
+        self.metrics.snapshot()
+    }
+
+    /// Returns a future that resolves after `ticks` units of the
+    /// executor's clock have elapsed.
+    ///
+    /// # Arguments
+    /// * `ticks` - Duration to wait, in the clock's tick units
+    pub fn sleep(&self, ticks: u64) -> Timer {
+        let deadline = self.clock.now().saturating_add(ticks);
+        Timer::new(deadline, self.timers.clone(), self.clock.clone())
+    }
+
+    /// Returns the nearest pending timer deadline, if any.
+    ///
+    /// Embedders can use this to arm a bounded one-shot hardware timer
+    /// before halting, instead of halting indefinitely.
+    pub fn next_timer_deadline(&self) -> Option<u64> {
+        self.timers.with(|heap| heap.next_deadline())
+    }
+
+    /// Spawns a future onto the executor, returning a [`JoinHandle`] that
+    /// resolves to its output once the executor has run it to completion.
+    ///
+    /// For fire-and-forget work, spawn a future with `Output = ()` and
+    /// call [`JoinHandle::detach`] on the returned handle; dropping the
+    /// handle instead cancels the task.
+    ///
+    /// # Arguments
+    /// * `future` - The future to run, producing a value of type `T`
+    ///
+    /// # Returns
+    /// A `JoinHandle<T>` that can be awaited for the task's output.
+    pub fn spawn<T: 'static>(&mut self, future: impl Future<Output = T> + 'static) -> JoinHandle<T> {
+        let (task, mut handle) = Task::new_with_handle(future);
+        handle.cancel_sink = Some(self.pending_cancellations.clone());
+        self.spawn_task(task);
+        handle
+    }
+
+    /// Spawns an already-constructed [`Task`] into the executor.
+    ///
+    /// This is the lower-level entry point used by [`Executor::spawn`];
+    /// call it directly when you already hold a `Task` (for example, one
+    /// produced by [`Task::new_with_handle`]).
+    ///
     /// # Arguments
     /// * `task` - The task to be executed
     ///
     /// # Panics
     /// * If a task with the same ID already exists
-    /// * If the task queue is full
-    pub fn spawn(&mut self, task: Task) {
+    pub fn spawn_task(&mut self, task: Task) {
         let task_id = task.id;
         if self.tasks.insert(task.id, task).is_some() {
             panic!("task with same ID already in tasks");
         }
-        self.task_queue.push(task_id).expect("queue full");
+        self.task_queue.push(task_id);
+        #[cfg(feature = "metrics")]
+        self.metrics.record_spawn();
+    }
+
+    /// Cancels a spawned task, dropping its future and cached waker.
+    ///
+    /// Any `FallibleJoinHandle` awaiting this task resolves to `None`; a
+    /// plain `JoinHandle` is left pending forever, since it has no way to
+    /// report cancellation.
+    ///
+    /// # Returns
+    /// `true` if the task was still running and has now been cancelled,
+    /// `false` if it had already completed (or never existed).
+    pub fn cancel(&mut self, id: TaskId) -> bool {
+        self.cancel_task(id)
+    }
+
+    /// Drops a task's future and cached waker, running its cancel hook
+    /// (if any) so an awaiting `FallibleJoinHandle` is woken with `None`.
+    fn cancel_task(&mut self, id: TaskId) -> bool {
+        match self.tasks.remove(&id) {
+            Some(task) => {
+                self.waker_cache.remove(&id);
+                if let Some(hook) = task.cancel_hook {
+                    hook();
+                }
+                #[cfg(feature = "metrics")]
+                self.metrics.record_cancellation();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Cancels every task requested by a dropped `JoinHandle` since the
+    /// last call. Called only from `run_ready_tasks`, before any task in
+    /// this pass is polled, so a task's future is never dropped while
+    /// it's being polled.
+    fn drain_pending_cancellations(&mut self) {
+        let pending = self.pending_cancellations.with(mem::take);
+        for id in pending {
+            self.cancel_task(id);
+        }
     }
 
-    /// Runs the executor in an infinite loop, processing ready tasks
-    /// and sleeping when idle.
-    /// 
+    /// Runs the executor in an infinite loop, processing ready tasks,
+    /// waking expired timers, and sleeping when idle.
+    ///
     /// # Returns
     /// Never returns (!) as it runs indefinitely
     pub fn run(&mut self) -> ! {
         loop {
             self.run_ready_tasks();
+            self.wake_expired_timers();
             self.sleep_if_idle();
         }
     }
 
+    /// Wakes every timer whose deadline has passed, pushing the owning
+    /// tasks back into `task_queue` via their cached wakers.
+    ///
+    /// Re-checks `clock.now()` here (rather than trusting the tick that
+    /// triggered this call) so a spurious early wakeup can't pop timers
+    /// that haven't actually expired yet.
+    fn wake_expired_timers(&self) {
+        let now = self.clock.now();
+        self.timers.with(|heap| heap.wake_expired(now));
+    }
+
     /// Processes all tasks currently in the task queue.
     /// Tasks that are Poll::Ready are removed, while Poll::Pending
     /// tasks remain in the executor.
     fn run_ready_tasks(&mut self) {
+        self.drain_pending_cancellations();
+
         // destructure `self` to avoid borrow checker errors
         let Self {
             tasks,
             task_queue,
             waker_cache,
+            #[cfg(feature = "metrics")]
+            metrics,
+            ..
         } = self;
 
         while let Some(task_id) = task_queue.pop() {
@@ -68,15 +212,24 @@ impl Executor {
                 Some(task) => task,
                 None => continue, // task no longer exists
             };
-            let waker = waker_cache
-                .entry(task_id)
-                .or_insert_with(|| TaskWaker::new(task_id, task_queue.clone()));
+            let waker = waker_cache.entry(task_id).or_insert_with(|| {
+                TaskWaker::new(
+                    task_id,
+                    task_queue.clone(),
+                    #[cfg(feature = "metrics")]
+                    metrics.clone(),
+                )
+            });
             let mut context = Context::from_waker(waker);
+            #[cfg(feature = "metrics")]
+            metrics.record_poll();
             match task.poll(&mut context) {
                 Poll::Ready(()) => {
                     // task done -> remove it and its cached waker
                     tasks.remove(&task_id);
                     waker_cache.remove(&task_id);
+                    #[cfg(feature = "metrics")]
+                    metrics.record_completion();
                 }
                 Poll::Pending => {}
             }
@@ -86,13 +239,20 @@ impl Executor {
     /// Puts the executor to sleep if there are no tasks to process.
     /// When x86_64_support is enabled, uses CPU-specific sleep instructions.
     /// Otherwise it does nothing.
+    ///
+    /// The halt here relies on the embedder's own periodic or one-shot
+    /// hardware timer interrupt to wake the CPU; use
+    /// [`Executor::next_timer_deadline`] to arm a one-shot timer bounded
+    /// by the earliest pending deadline so the halt never outlasts it.
     fn sleep_if_idle(&self) {
         #[cfg(feature = "x86_64_support")]
         {
             use x86_64::instructions::interrupts::{self, enable_and_hlt};
-    
+
             interrupts::disable();
             if self.task_queue.is_empty() {
+                #[cfg(feature = "metrics")]
+                self.metrics.record_idle_transition();
                 enable_and_hlt();
             } else {
                 interrupts::enable();
@@ -102,33 +262,41 @@ impl Executor {
 }
 
 /// Provides the wake mechanism for tasks in the executor.
-/// Uses an ArrayQueue to push tasks back into the ready queue.
+/// Uses a SegQueue to push tasks back into the ready queue.
 struct TaskWaker {
     /// ID of the task this waker is associated with
     task_id: TaskId,
     /// Reference to the executor's task queue
-    task_queue: Arc<ArrayQueue<TaskId>>,
+    task_queue: Arc<SegQueue<TaskId>>,
+    /// Counters to bump when this waker fires, if the `metrics` feature is enabled
+    #[cfg(feature = "metrics")]
+    metrics: Arc<Metrics>,
 }
 
 impl TaskWaker {
     /// Creates a new Waker for a specific task.
-    /// 
+    ///
     /// # Arguments
     /// * `task_id` - ID of the task to wake
     /// * `task_queue` - Queue to push the task ID into when woken
-    fn new(task_id: TaskId, task_queue: Arc<ArrayQueue<TaskId>>) -> Waker {
+    fn new(
+        task_id: TaskId,
+        task_queue: Arc<SegQueue<TaskId>>,
+        #[cfg(feature = "metrics")] metrics: Arc<Metrics>,
+    ) -> Waker {
         Waker::from(Arc::new(TaskWaker {
             task_id,
             task_queue,
+            #[cfg(feature = "metrics")]
+            metrics,
         }))
     }
 
     /// Pushes the task ID back into the queue, marking it as ready to run.
-    /// 
-    /// # Panics
-    /// If the task queue is full
     fn wake_task(&self) {
-        self.task_queue.push(self.task_id).expect("task_queue full");
+        self.task_queue.push(self.task_id);
+        #[cfg(feature = "metrics")]
+        self.metrics.record_wakeup();
     }
 }
 
@@ -142,4 +310,125 @@ impl Wake for TaskWaker {
     fn wake_by_ref(self: &Arc<Self>) {
         self.wake_task();
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simple_executor::dummy_waker;
+    use core::pin::Pin;
+
+    struct FakeClock;
+
+    impl Clock for FakeClock {
+        fn now(&self) -> u64 {
+            0
+        }
+    }
+
+    /// A future that never resolves, so its task stays pending until cancelled.
+    struct Never;
+
+    impl Future for Never {
+        type Output = ();
+
+        fn poll(self: Pin<&mut Self>, _context: &mut Context) -> Poll<()> {
+            Poll::Pending
+        }
+    }
+
+    /// Pending `yields_remaining` times, waking itself each time, before resolving.
+    struct YieldNTimes {
+        yields_remaining: u32,
+    }
+
+    impl Future for YieldNTimes {
+        type Output = ();
+
+        fn poll(mut self: Pin<&mut Self>, context: &mut Context) -> Poll<()> {
+            if self.yields_remaining == 0 {
+                Poll::Ready(())
+            } else {
+                self.yields_remaining -= 1;
+                context.waker().wake_by_ref();
+                Poll::Pending
+            }
+        }
+    }
+
+    #[test]
+    fn task_queue_handles_far_more_than_the_old_100_task_wakeup_cap() {
+        // Before the switch to an unbounded SegQueue, the task queue was a
+        // bounded ArrayQueue capped at 100 entries; pushing past that
+        // panicked. Spawn well past that many tasks, each rescheduling
+        // itself several times, so the queue holds far more than 100
+        // entries at once - and assert every task still runs to completion
+        // without panicking.
+        let mut executor = Executor::new(FakeClock);
+        let handles: Vec<_> = (0..500)
+            .map(|_| executor.spawn(YieldNTimes { yields_remaining: 5 }))
+            .collect();
+
+        // Each task reschedules itself 5 times before completing, so this
+        // drains the full backlog regardless of how the queue interleaves
+        // tasks within a single pass.
+        for _ in 0..6 {
+            executor.run_ready_tasks();
+        }
+
+        assert!(executor.tasks.is_empty());
+        for handle in handles {
+            handle.detach();
+        }
+    }
+
+    #[test]
+    fn cancelling_a_pending_task_resolves_its_fallible_handle_to_none() {
+        let mut executor = Executor::new(FakeClock);
+        let handle = executor.spawn(Never);
+        let id = handle.id();
+        let mut fallible = handle.fallible();
+
+        // Run one pass so the task is actually registered and pending,
+        // mirroring the "only cancel between polls" invariant: cancelling
+        // below happens after this pass has returned, never during it.
+        executor.run_ready_tasks();
+        assert!(executor.tasks.contains_key(&id));
+
+        assert!(executor.cancel(id));
+        assert!(!executor.tasks.contains_key(&id));
+
+        let waker = dummy_waker();
+        let mut context = Context::from_waker(&waker);
+        assert_eq!(Pin::new(&mut fallible).poll(&mut context), Poll::Ready(None));
+    }
+
+    #[cfg(feature = "metrics")]
+    #[test]
+    fn metrics_snapshot_reflects_spawns_polls_completions_and_cancellations() {
+        let mut executor = Executor::new(FakeClock);
+
+        let completed = executor.spawn(YieldNTimes { yields_remaining: 1 });
+        let cancelled = executor.spawn(Never);
+        let cancelled_id = cancelled.id();
+        cancelled.detach();
+
+        // A single pass drains `completed`'s self-reschedule along with its
+        // initial poll, since `run_ready_tasks` keeps popping until the
+        // queue is empty; `cancelled` is left pending since `Never` never
+        // wakes itself.
+        executor.run_ready_tasks();
+
+        assert!(executor.cancel(cancelled_id));
+
+        let snapshot = executor.metrics();
+        assert_eq!(snapshot.spawned, 2);
+        assert_eq!(snapshot.completed, 1);
+        assert_eq!(snapshot.cancelled, 1);
+        assert_eq!(snapshot.live_tasks, 0);
+        assert!(snapshot.polls >= 3); // completed polled twice, cancelled at least once
+        assert!(snapshot.wakeups >= 1); // completed's self-wake between its two polls
+
+        completed.detach();
+    }
 }
\ No newline at end of file
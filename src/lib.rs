@@ -21,6 +21,10 @@ The crate is `no_std` compatible and uses the `alloc` crate for heap allocations
   When enabled, this will cause `exo_task` to use the standard library.
   This is enabled by default but can be disabled with `default-features = false,`
   in your`Cargo.toml`.
+* **metrics** -
+  When enabled, `Executor` records spawn/poll/completion/wakeup/idle
+  counters and live-task count, readable via `Executor::metrics`. Off by
+  default so the hot path pays no recording overhead.
 */
 
 extern crate alloc;
@@ -35,4 +39,19 @@ pub mod executor;
 pub mod simple_executor;
 
 /// Event bus for managing type-erased events and listeners
-pub mod events;
\ No newline at end of file
+pub mod events;
+
+/// Timer driver: a deadline-ordered heap of pending timers and the
+/// `Timer` future used to await them
+pub mod timer;
+
+/// Single-threaded executor for `!Send` futures, using `Rc`-backed
+/// wakers instead of atomics
+pub mod local_executor;
+
+/// Executor instrumentation, gated behind the `metrics` feature
+#[cfg(feature = "metrics")]
+pub mod metrics;
+
+/// Small internal synchronization primitives shared across modules
+mod sync;
\ No newline at end of file
@@ -0,0 +1,272 @@
+use crate::sync::SpinMutex;
+use crate::task::{JoinHandle, Task, TaskId};
+use alloc::{
+    collections::{BTreeMap, VecDeque},
+    rc::Rc,
+    sync::Arc,
+    task::Wake,
+    vec::Vec,
+};
+use core::{
+    cell::RefCell,
+    future::Future,
+    mem,
+    task::{Context, Poll, Waker},
+};
+
+/// A single-threaded executor for `!Send` futures.
+///
+/// `LocalExecutor` places no `Send` bound on spawned futures and keeps
+/// task storage behind `Rc<RefCell<..>>`, so callers can spawn futures
+/// that hold `Rc` or other non-`Send` state (for example, blog_os's
+/// keyboard-stream pattern of `Rc`-shared state across tasks) - those
+/// tasks never move
+/// off the thread that owns this executor, since `LocalExecutor` itself
+/// is `!Send`/`!Sync` by virtue of holding an `Rc`.
+///
+/// The wakers it hands out are a different matter: a `Waker`'s safety
+/// contract requires every vtable function be callable from any thread
+/// (that's why `Waker` is unconditionally `Send + Sync`), and a `Waker`
+/// handed to a polled future can end up stored anywhere `Send + Sync`
+/// reaches - including this crate's own `JoinShared`/`EventSlot` wakers,
+/// which are legitimately `Arc`-shared across threads. So `LocalTaskWaker`
+/// still pushes only a plain `TaskId` into a `SpinMutex`-guarded queue,
+/// same as [`crate::executor::Executor`]'s `TaskWaker`: waking from
+/// another thread is then just a synchronized queue push, not a data
+/// race on an `Rc`'s refcount.
+pub struct LocalExecutor {
+    /// Stores all tasks indexed by their TaskId
+    tasks: Rc<RefCell<BTreeMap<TaskId, Task>>>,
+    /// Queue of task IDs that are ready to be polled. Guarded by a
+    /// `SpinMutex` rather than kept behind `Rc<RefCell<..>>` so wakers
+    /// handed out by this executor are soundly `Send + Sync`.
+    task_queue: Arc<SpinMutex<VecDeque<TaskId>>>,
+    /// Cache of wakers for each task to avoid recreation
+    waker_cache: RefCell<BTreeMap<TaskId, Waker>>,
+    /// Task IDs whose `JoinHandle` was dropped and which should be
+    /// cancelled at the next safe point (the start of `run_until_stalled`)
+    pending_cancellations: Arc<SpinMutex<Vec<TaskId>>>,
+}
+
+impl LocalExecutor {
+    /// Creates a new LocalExecutor with empty task collections.
+    pub fn new() -> Self {
+        LocalExecutor {
+            tasks: Rc::new(RefCell::new(BTreeMap::new())),
+            task_queue: Arc::new(SpinMutex::new(VecDeque::new())),
+            waker_cache: RefCell::new(BTreeMap::new()),
+            pending_cancellations: Arc::new(SpinMutex::new(Vec::new())),
+        }
+    }
+
+    /// Spawns a `!Send` future onto the executor, returning a
+    /// [`JoinHandle`] that resolves to its output once it has run to
+    /// completion.
+    ///
+    /// # Arguments
+    /// * `future` - The future to run, producing a value of type `T`
+    ///
+    /// # Returns
+    /// A `JoinHandle<T>` that can be awaited for the task's output.
+    pub fn spawn<T: 'static>(&self, future: impl Future<Output = T> + 'static) -> JoinHandle<T> {
+        let (task, mut handle) = Task::new_with_handle(future);
+        handle.cancel_sink = Some(self.pending_cancellations.clone());
+        self.spawn_task(task);
+        handle
+    }
+
+    /// Spawns an already-constructed [`Task`] into the executor.
+    ///
+    /// # Panics
+    /// If a task with the same ID already exists
+    pub fn spawn_task(&self, task: Task) {
+        let task_id = task.id;
+        if self.tasks.borrow_mut().insert(task.id, task).is_some() {
+            panic!("task with same ID already in tasks");
+        }
+        self.task_queue.with(|queue| queue.push_back(task_id));
+    }
+
+    /// Cancels a spawned task, dropping its future and cached waker.
+    ///
+    /// Mirrors [`crate::executor::Executor::cancel`]; see its docs for how
+    /// this affects an awaiting `FallibleJoinHandle`.
+    ///
+    /// # Returns
+    /// `true` if the task was still running and has now been cancelled,
+    /// `false` if it had already completed (or never existed).
+    pub fn cancel(&self, id: TaskId) -> bool {
+        self.cancel_task(id)
+    }
+
+    /// Drops a task's future and cached waker, running its cancel hook
+    /// (if any) so an awaiting `FallibleJoinHandle` is woken with `None`.
+    fn cancel_task(&self, id: TaskId) -> bool {
+        match self.tasks.borrow_mut().remove(&id) {
+            Some(task) => {
+                self.waker_cache.borrow_mut().remove(&id);
+                if let Some(hook) = task.cancel_hook {
+                    hook();
+                }
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Cancels every task requested by a dropped `JoinHandle` since the
+    /// last call. Called only from `run_until_stalled`, before any task in
+    /// this pass is polled, so a task's future is never dropped while it's
+    /// being polled.
+    fn drain_pending_cancellations(&self) {
+        let pending = self.pending_cancellations.with(mem::take);
+        for id in pending {
+            self.cancel_task(id);
+        }
+    }
+
+    /// Runs the executor in an infinite loop, draining ready tasks and
+    /// sleeping when idle.
+    ///
+    /// # Returns
+    /// Never returns (!) as it runs indefinitely
+    pub fn run(&self) -> ! {
+        loop {
+            self.run_until_stalled();
+            self.sleep_if_idle();
+        }
+    }
+
+    /// Polls every ready task until none remain, then returns. Tasks
+    /// woken afterwards (for example, from an interrupt handler running
+    /// on the same core) are picked up by the next call.
+    ///
+    /// A task is removed from `tasks` before it's polled and only
+    /// reinserted if it's still pending, so a task that spawns another
+    /// task from within its own poll doesn't re-borrow an already
+    /// borrowed `RefCell`.
+    pub fn run_until_stalled(&self) {
+        self.drain_pending_cancellations();
+        loop {
+            let task_id = match self.task_queue.with(|queue| queue.pop_front()) {
+                Some(task_id) => task_id,
+                None => break,
+            };
+            let mut task = match self.tasks.borrow_mut().remove(&task_id) {
+                Some(task) => task,
+                None => continue, // task no longer exists
+            };
+            let waker = self
+                .waker_cache
+                .borrow_mut()
+                .entry(task_id)
+                .or_insert_with(|| LocalTaskWaker::new(task_id, self.task_queue.clone()))
+                .clone();
+            let mut context = Context::from_waker(&waker);
+            match task.poll(&mut context) {
+                Poll::Ready(()) => {
+                    // task done -> drop it and its cached waker
+                    self.waker_cache.borrow_mut().remove(&task_id);
+                }
+                Poll::Pending => {
+                    self.tasks.borrow_mut().insert(task_id, task);
+                }
+            }
+        }
+    }
+
+    /// Puts the CPU to sleep if there are no tasks to process.
+    /// When x86_64_support is enabled, uses CPU-specific sleep instructions.
+    /// Otherwise it does nothing.
+    fn sleep_if_idle(&self) {
+        #[cfg(feature = "x86_64_support")]
+        {
+            use x86_64::instructions::interrupts::{self, enable_and_hlt};
+
+            interrupts::disable();
+            if self.task_queue.with(|queue| queue.is_empty()) {
+                enable_and_hlt();
+            } else {
+                interrupts::enable();
+            }
+        }
+    }
+}
+
+/// Provides the wake mechanism for a [`LocalExecutor`]'s tasks.
+///
+/// Structurally identical to [`crate::executor::Executor`]'s `TaskWaker`:
+/// the only state a woken-from-anywhere `Waker` needs is a `TaskId` (plain
+/// data) and a thread-safe queue to push it into, so there's no need for
+/// `Rc`/non-atomic refcounting here at all.
+struct LocalTaskWaker {
+    /// ID of the task this waker is associated with
+    task_id: TaskId,
+    /// Reference to the executor's task queue
+    task_queue: Arc<SpinMutex<VecDeque<TaskId>>>,
+}
+
+impl LocalTaskWaker {
+    /// Creates a new Waker for a specific task.
+    ///
+    /// # Arguments
+    /// * `task_id` - ID of the task to wake
+    /// * `task_queue` - Queue to push the task ID into when woken
+    fn new(task_id: TaskId, task_queue: Arc<SpinMutex<VecDeque<TaskId>>>) -> Waker {
+        Waker::from(Arc::new(LocalTaskWaker { task_id, task_queue }))
+    }
+
+    /// Pushes the task ID back into the queue, marking it as ready to run.
+    fn wake_task(&self) {
+        self.task_queue.with(|queue| queue.push_back(self.task_id));
+    }
+}
+
+impl Wake for LocalTaskWaker {
+    /// Wakes a task by consuming the waker
+    fn wake(self: Arc<Self>) {
+        self.wake_task();
+    }
+
+    /// Wakes a task by reference
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.wake_task();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simple_executor::block_on;
+    use core::pin::Pin;
+
+    /// Pending on the first poll, waking itself via the local waker before
+    /// returning; Ready on the second. Exercises `LocalTaskWaker`'s round
+    /// trip: `wake_by_ref` pushes this task back onto `task_queue`, and the
+    /// still-running `run_until_stalled` loop picks it straight back up.
+    struct YieldOnce {
+        yielded: bool,
+    }
+
+    impl Future for YieldOnce {
+        type Output = u32;
+
+        fn poll(mut self: Pin<&mut Self>, context: &mut Context) -> Poll<u32> {
+            if self.yielded {
+                Poll::Ready(7)
+            } else {
+                self.yielded = true;
+                context.waker().wake_by_ref();
+                Poll::Pending
+            }
+        }
+    }
+
+    #[test]
+    fn local_waker_reschedules_a_task_that_wakes_itself() {
+        let executor = LocalExecutor::new();
+        let handle = executor.spawn(YieldOnce { yielded: false });
+        executor.run_until_stalled();
+        assert_eq!(block_on(handle), 7);
+    }
+}
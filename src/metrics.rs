@@ -0,0 +1,108 @@
+use core::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+
+/// Atomic counters tracking [`crate::executor::Executor`] activity.
+///
+/// Modeled on Fuchsia's `Collector`/`LocalCollector` and tokio's runtime
+/// metrics. Only compiled in when the `metrics` feature is enabled, so
+/// the executor's hot path pays no recording overhead otherwise.
+#[derive(Default)]
+pub struct Metrics {
+    /// Total tasks ever spawned
+    spawned: AtomicU64,
+    /// Total tasks that ran to completion
+    completed: AtomicU64,
+    /// Total tasks cancelled before completion
+    cancelled: AtomicU64,
+    /// Total calls to `Task::poll` across all tasks
+    polls: AtomicU64,
+    /// Total times a waker fired, rescheduling a task
+    wakeups: AtomicU64,
+    /// Total times the executor halted the CPU for being idle
+    idle_transitions: AtomicU64,
+    /// Tasks currently spawned but not yet completed
+    live_tasks: AtomicUsize,
+}
+
+impl Metrics {
+    pub(crate) const fn new() -> Self {
+        Metrics {
+            spawned: AtomicU64::new(0),
+            completed: AtomicU64::new(0),
+            cancelled: AtomicU64::new(0),
+            polls: AtomicU64::new(0),
+            wakeups: AtomicU64::new(0),
+            idle_transitions: AtomicU64::new(0),
+            live_tasks: AtomicUsize::new(0),
+        }
+    }
+
+    pub(crate) fn record_spawn(&self) {
+        self.spawned.fetch_add(1, Ordering::Relaxed);
+        self.live_tasks.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_poll(&self) {
+        self.polls.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_completion(&self) {
+        self.completed.fetch_add(1, Ordering::Relaxed);
+        self.live_tasks.fetch_sub(1, Ordering::Relaxed);
+    }
+
+    /// Records a task cancelled via `Executor::cancel` or a dropped
+    /// `JoinHandle`, before it ran to completion.
+    pub(crate) fn record_cancellation(&self) {
+        self.cancelled.fetch_add(1, Ordering::Relaxed);
+        self.live_tasks.fetch_sub(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_wakeup(&self) {
+        self.wakeups.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Only compiled in alongside `x86_64_support`, whose `sleep_if_idle`
+    /// halt path is this counter's only call site - kept out of plain
+    /// `metrics` builds so it isn't reported as dead code.
+    #[cfg(feature = "x86_64_support")]
+    pub(crate) fn record_idle_transition(&self) {
+        self.idle_transitions.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Takes a point-in-time copy of every counter.
+    ///
+    /// Each field is loaded independently with `Relaxed` ordering, so
+    /// under concurrent activity the snapshot is a best-effort view
+    /// rather than a linearizable one - the same tradeoff tokio's
+    /// runtime metrics make.
+    pub fn snapshot(&self) -> MetricsSnapshot {
+        MetricsSnapshot {
+            spawned: self.spawned.load(Ordering::Relaxed),
+            completed: self.completed.load(Ordering::Relaxed),
+            cancelled: self.cancelled.load(Ordering::Relaxed),
+            polls: self.polls.load(Ordering::Relaxed),
+            wakeups: self.wakeups.load(Ordering::Relaxed),
+            idle_transitions: self.idle_transitions.load(Ordering::Relaxed),
+            live_tasks: self.live_tasks.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// A point-in-time copy of an executor's [`Metrics`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MetricsSnapshot {
+    /// Total tasks ever spawned
+    pub spawned: u64,
+    /// Total tasks that ran to completion
+    pub completed: u64,
+    /// Total tasks cancelled before completion
+    pub cancelled: u64,
+    /// Total calls to `Task::poll` across all tasks
+    pub polls: u64,
+    /// Total times a waker fired, rescheduling a task
+    pub wakeups: u64,
+    /// Total times the executor halted the CPU for being idle
+    pub idle_transitions: u64,
+    /// Tasks currently spawned but not yet completed
+    pub live_tasks: usize,
+}
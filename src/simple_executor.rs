@@ -1,6 +1,11 @@
-use crate::task::Task;
+use crate::task::{JoinHandle, Task};
 use alloc::collections::VecDeque;
-use core::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+use core::{
+    future::Future,
+    pin::pin,
+    sync::atomic::{AtomicBool, Ordering},
+    task::{Context, Poll, RawWaker, RawWakerVTable, Waker},
+};
 
 /// A basic task executor that runs tasks in a FIFO queue.
 /// This implementation uses a dummy waker that does nothing when woken.
@@ -17,12 +22,21 @@ impl SimpleExecutor {
         }
     }
 
-    /// Adds a new task to the back of the task queue.
-    /// 
+    /// Spawns a future onto the executor, returning a [`JoinHandle`] that
+    /// resolves to its output once the future has run to completion.
+    ///
+    /// For fire-and-forget work, spawn a future with `Output = ()` and
+    /// drop the returned handle.
+    ///
     /// # Arguments
-    /// * `task` - The task to be executed
-    pub fn spawn(&mut self, task: Task) {
-        self.task_queue.push_back(task)
+    /// * `future` - The future to run, producing a value of type `T`
+    ///
+    /// # Returns
+    /// A `JoinHandle<T>` that can be awaited for the task's output.
+    pub fn spawn<T: 'static>(&mut self, future: impl Future<Output = T> + 'static) -> JoinHandle<T> {
+        let (task, handle) = Task::new_with_handle(future);
+        self.task_queue.push_back(task);
+        handle
     }
 
     /// Runs all tasks in the queue until completion.
@@ -39,6 +53,86 @@ impl SimpleExecutor {
     }
 }
 
+/// Drives a single future to completion on the current CPU, without
+/// standing up a full [`SimpleExecutor`] or [`crate::executor::Executor`].
+///
+/// Modeled on zynq-rs's single-future executor: the waker is backed by an
+/// `AtomicBool` "ready" flag rather than `dummy_waker`'s no-op, so a
+/// future that genuinely yields parks the CPU instead of busy-polling.
+///
+/// # Arguments
+/// * `future` - The future to run to completion
+///
+/// # Returns
+/// The future's output value.
+///
+/// # Caution: don't await an `Executor`-driven `Timer` here
+///
+/// Nothing but [`crate::executor::Executor::run`]'s loop calls
+/// `wake_expired_timers` to pop and wake entries out of an `Executor`'s
+/// timer heap. `block_on` only drives the one future passed to it, so
+/// `block_on(executor.sleep(n))` registers this waker in that heap and
+/// then waits forever: the heap is never polled from anywhere else, so
+/// the waker is never called and `ready` never gets set. Only await
+/// futures here that wake themselves independently of an `Executor`'s
+/// timer/task machinery.
+pub fn block_on<T>(future: impl Future<Output = T>) -> T {
+    let mut future = pin!(future);
+    let ready = AtomicBool::new(false);
+    let waker = unsafe { Waker::from_raw(block_on_raw_waker(&ready)) };
+    let mut context = Context::from_waker(&waker);
+
+    loop {
+        match future.as_mut().poll(&mut context) {
+            Poll::Ready(value) => return value,
+            Poll::Pending => park_until_ready(&ready),
+        }
+    }
+}
+
+/// Parks the CPU until `ready` is set, then clears it before returning.
+fn park_until_ready(ready: &AtomicBool) {
+    #[cfg(feature = "x86_64_support")]
+    {
+        use x86_64::instructions::interrupts::{self, enable_and_hlt};
+
+        loop {
+            interrupts::disable();
+            if ready.load(Ordering::Acquire) {
+                interrupts::enable();
+                break;
+            }
+            enable_and_hlt();
+        }
+    }
+    #[cfg(not(feature = "x86_64_support"))]
+    {
+        while !ready.load(Ordering::Acquire) {
+            core::hint::spin_loop();
+        }
+    }
+    ready.store(false, Ordering::Release);
+}
+
+/// Builds a `RawWaker` over an `&AtomicBool` "ready" flag: waking it just
+/// sets the flag with `Release` ordering for [`park_until_ready`] to observe.
+fn block_on_raw_waker(ready: &AtomicBool) -> RawWaker {
+    unsafe fn clone(ptr: *const ()) -> RawWaker {
+        RawWaker::new(ptr, &BLOCK_ON_VTABLE)
+    }
+    unsafe fn wake(ptr: *const ()) {
+        wake_by_ref(ptr);
+    }
+    unsafe fn wake_by_ref(ptr: *const ()) {
+        (*(ptr as *const AtomicBool)).store(true, Ordering::Release);
+    }
+    unsafe fn drop_waker(_: *const ()) {}
+
+    static BLOCK_ON_VTABLE: RawWakerVTable = RawWakerVTable::new(clone, wake, wake_by_ref, drop_waker);
+
+    RawWaker::new(ready as *const AtomicBool as *const (), &BLOCK_ON_VTABLE)
+}
+
 /// Creates a RawWaker with no-op implementations of all required methods.
 /// This is used for tasks that don't need to be woken up externally.
 /// 
@@ -56,10 +150,42 @@ fn dummy_raw_waker() -> RawWaker {
 }
 
 /// Creates a Waker from the dummy RawWaker.
-/// 
+///
 /// # Safety
 /// This is safe because the dummy_raw_waker implements all required methods,
 /// albeit as no-ops.
-fn dummy_waker() -> Waker {
+pub(crate) fn dummy_waker() -> Waker {
     unsafe { Waker::from_raw(dummy_raw_waker()) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Pending on the first poll, waking itself before returning; Ready on
+    /// the second. Exercises `block_on`'s `RawWaker` round trip:
+    /// `wake_by_ref` sets `ready`, and `park_until_ready` observes it
+    /// already set instead of actually parking.
+    struct YieldOnce {
+        yielded: bool,
+    }
+
+    impl Future for YieldOnce {
+        type Output = u32;
+
+        fn poll(mut self: core::pin::Pin<&mut Self>, context: &mut Context) -> Poll<u32> {
+            if self.yielded {
+                Poll::Ready(7)
+            } else {
+                self.yielded = true;
+                context.waker().wake_by_ref();
+                Poll::Pending
+            }
+        }
+    }
+
+    #[test]
+    fn block_on_resumes_after_its_waker_fires() {
+        assert_eq!(block_on(YieldOnce { yielded: false }), 7);
+    }
 }
\ No newline at end of file
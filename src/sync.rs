@@ -0,0 +1,42 @@
+use core::{
+    cell::UnsafeCell,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+/// A minimal spinning mutex for small, briefly-held critical sections
+/// (stashing a value, taking a value, cloning a waker).
+///
+/// This avoids pulling in a full lock implementation just to guard a
+/// handful of words, keeping the crate `no_std`-friendly without extra
+/// dependencies.
+pub(crate) struct SpinMutex<T> {
+    locked: AtomicBool,
+    value: UnsafeCell<T>,
+}
+
+// Safety: access to `value` is only ever granted while `locked` is held.
+unsafe impl<T: Send> Sync for SpinMutex<T> {}
+
+impl<T> SpinMutex<T> {
+    pub(crate) const fn new(value: T) -> Self {
+        SpinMutex {
+            locked: AtomicBool::new(false),
+            value: UnsafeCell::new(value),
+        }
+    }
+
+    pub(crate) fn with<R>(&self, f: impl FnOnce(&mut T) -> R) -> R {
+        while self
+            .locked
+            .compare_exchange_weak(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .is_err()
+        {
+            core::hint::spin_loop();
+        }
+        // Safety: the compare-exchange above gives us exclusive access
+        // until `locked` is released below.
+        let result = f(unsafe { &mut *self.value.get() });
+        self.locked.store(false, Ordering::Release);
+        result
+    }
+}
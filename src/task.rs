@@ -1,9 +1,10 @@
-use alloc::boxed::Box;
+use crate::sync::SpinMutex;
+use alloc::{boxed::Box, sync::Arc};
 use core::{
     future::Future,
     pin::Pin,
-    sync::atomic::{AtomicU64, Ordering},
-    task::{Context, Poll},
+    sync::atomic::{AtomicBool, AtomicU64, Ordering},
+    task::{Context, Poll, Waker},
 };
 
 /// Represents an asynchronous task that can be executed by the executor.
@@ -13,11 +14,14 @@ pub struct Task {
     pub(crate) id: TaskId,
     /// The actual future that will be polled to completion
     pub(crate) future: Pin<Box<dyn Future<Output = ()>>>,
+    /// Run once if the task is cancelled before completing, so a
+    /// `FallibleJoinHandle` awaiting it can observe the cancellation
+    pub(crate) cancel_hook: Option<Box<dyn FnOnce()>>,
 }
 
 impl Task {
     /// Creates a new task from a future.
-    /// 
+    ///
     /// # Arguments
     /// * `future` - Any future that returns () and has a static lifetime
     ///
@@ -27,11 +31,55 @@ impl Task {
         Task {
             id: TaskId::new(),
             future: Box::pin(future),
+            cancel_hook: None,
         }
     }
 
+    /// Wraps a future with an output-carrying [`JoinHandle`].
+    ///
+    /// The returned task drives `future` to completion and stashes its
+    /// output in the handle's shared slot, waking anyone polling the
+    /// handle once the value is available. Used by `spawn`-style APIs
+    /// that need to hand a result back to the caller.
+    ///
+    /// # Arguments
+    /// * `future` - The future to run, producing a value of type `T`
+    ///
+    /// # Returns
+    /// A tuple of the `Task` to spawn and the `JoinHandle<T>` that
+    /// resolves to its output.
+    pub fn new_with_handle<T: 'static>(
+        future: impl Future<Output = T> + 'static,
+    ) -> (Task, JoinHandle<T>) {
+        let shared = Arc::new(JoinShared {
+            output: SpinMutex::new(None),
+            waker: SpinMutex::new(None),
+            cancelled: AtomicBool::new(false),
+        });
+        let mut task = Task::new(JoinFuture {
+            future: Box::pin(future),
+            shared: shared.clone(),
+        });
+        let cancel_shared = shared.clone();
+        task.cancel_hook = Some(Box::new(move || {
+            cancel_shared.cancelled.store(true, Ordering::Release);
+            if let Some(waker) = cancel_shared.waker.with(|slot| slot.take()) {
+                waker.wake();
+            }
+        }));
+        let id = task.id;
+        (
+            task,
+            JoinHandle {
+                id,
+                shared,
+                cancel_sink: None,
+            },
+        )
+    }
+
     /// Polls the internal future to make progress on the task.
-    /// 
+    ///
     /// # Arguments
     /// * `context` - The task context containing the waker
     ///
@@ -42,10 +90,152 @@ impl Task {
     }
 }
 
+/// Bridges a [`JoinHandle`] back to the executor that spawned it, so
+/// dropping the handle (without calling [`JoinHandle::detach`]) can
+/// request cancellation of the underlying task.
+pub(crate) trait CancelSink: Send + Sync {
+    fn request_cancel(&self, id: TaskId);
+}
+
+/// State shared between a [`JoinFuture`] and its [`JoinHandle`].
+struct JoinShared<T> {
+    /// The task's output once it has completed
+    output: SpinMutex<Option<T>>,
+    /// Waker registered by a pending `JoinHandle::poll`, if any
+    waker: SpinMutex<Option<Waker>>,
+    /// Set by the task's `cancel_hook` if it is cancelled before completing
+    cancelled: AtomicBool,
+}
+
+/// Adapts a `Future<Output = T>` into a `Future<Output = ()>` so it can be
+/// stored in a [`Task`], stashing its output in `shared` and waking the
+/// corresponding [`JoinHandle`] on completion.
+struct JoinFuture<T> {
+    future: Pin<Box<dyn Future<Output = T>>>,
+    shared: Arc<JoinShared<T>>,
+}
+
+impl<T> Future for JoinFuture<T> {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, context: &mut Context) -> Poll<()> {
+        // Safety: `future` and `shared` are never moved out of `self`.
+        let this = unsafe { self.get_unchecked_mut() };
+        match this.future.as_mut().poll(context) {
+            Poll::Ready(output) => {
+                this.shared.output.with(|slot| *slot = Some(output));
+                if let Some(waker) = this.shared.waker.with(|slot| slot.take()) {
+                    waker.wake();
+                }
+                Poll::Ready(())
+            }
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+/// A handle to a spawned task's eventual output.
+///
+/// `JoinHandle<T>` is itself a future: awaiting it resolves to the `T`
+/// produced by the task once the executor has run it to completion.
+///
+/// When spawned via [`crate::executor::Executor`] or
+/// [`crate::local_executor::LocalExecutor`], dropping a `JoinHandle`
+/// cancels the task it tracks, mirroring async-task's default; call
+/// [`JoinHandle::detach`] to let the task run to completion on its own
+/// instead. [`crate::simple_executor::SimpleExecutor`] doesn't wire up a
+/// cancellation channel, so for tasks spawned there, dropping a handle is
+/// a no-op and the task always runs to completion.
+pub struct JoinHandle<T> {
+    /// ID of the task this handle tracks
+    id: TaskId,
+    shared: Arc<JoinShared<T>>,
+    /// Where to report cancellation if this handle is dropped; `None`
+    /// once the executor has no cancellation channel (or after `detach`)
+    pub(crate) cancel_sink: Option<Arc<dyn CancelSink>>,
+}
+
+impl<T> JoinHandle<T> {
+    /// Returns the ID of the task this handle tracks.
+    pub fn id(&self) -> TaskId {
+        self.id
+    }
+
+    /// Lets the underlying task keep running after this handle is
+    /// dropped, instead of being cancelled.
+    pub fn detach(mut self) {
+        self.cancel_sink = None;
+    }
+
+    /// Converts this handle into a [`FallibleJoinHandle`] that resolves
+    /// to `None` if the task is cancelled instead of hanging forever.
+    pub fn fallible(self) -> FallibleJoinHandle<T> {
+        FallibleJoinHandle { inner: self }
+    }
+}
+
+impl<T> Future for JoinHandle<T> {
+    type Output = T;
+
+    fn poll(self: Pin<&mut Self>, context: &mut Context) -> Poll<T> {
+        // Register the waker *before* checking the output slot: if the
+        // order were reversed, the task could complete (store the output
+        // and take+call a waker) in the gap between our check and our
+        // registration, and the waker we register afterwards would never
+        // be called even though a value is sitting in the slot.
+        self.shared
+            .waker
+            .with(|slot| *slot = Some(context.waker().clone()));
+        if let Some(output) = self.shared.output.with(|slot| slot.take()) {
+            return Poll::Ready(output);
+        }
+        Poll::Pending
+    }
+}
+
+impl<T> Drop for JoinHandle<T> {
+    fn drop(&mut self) {
+        if let Some(sink) = &self.cancel_sink {
+            sink.request_cancel(self.id);
+        }
+    }
+}
+
+/// A [`JoinHandle`] variant for tasks that may be cancelled.
+///
+/// Awaiting it resolves to `Some(T)` if the task completed normally, or
+/// `None` if it was cancelled (via [`crate::executor::Executor::cancel`])
+/// before producing a value. Like `JoinHandle`, dropping it cancels the
+/// task; it has no `detach` of its own, and a `JoinHandle` consumed by
+/// [`JoinHandle::detach`] can no longer be converted into one.
+pub struct FallibleJoinHandle<T> {
+    inner: JoinHandle<T>,
+}
+
+impl<T> Future for FallibleJoinHandle<T> {
+    type Output = Option<T>;
+
+    fn poll(self: Pin<&mut Self>, context: &mut Context) -> Poll<Option<T>> {
+        let shared = &self.inner.shared;
+        // See `JoinHandle::poll` for why the waker is registered before
+        // the output (and here, cancellation) slots are checked.
+        shared
+            .waker
+            .with(|slot| *slot = Some(context.waker().clone()));
+        if let Some(output) = shared.output.with(|slot| slot.take()) {
+            return Poll::Ready(Some(output));
+        }
+        if shared.cancelled.load(Ordering::Acquire) {
+            return Poll::Ready(None);
+        }
+        Poll::Pending
+    }
+}
+
 /// Represents a unique identifier for a task.
 /// Implemented as a newtype pattern around u64 for type safety.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
-pub(crate) struct TaskId(u64);
+pub struct TaskId(u64);
 
 impl TaskId {
     /// Creates a new unique TaskId using an atomic counter.
@@ -56,4 +246,18 @@ impl TaskId {
         static NEXT_ID: AtomicU64 = AtomicU64::new(0);
         TaskId(NEXT_ID.fetch_add(1, Ordering::Relaxed))
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::simple_executor::{block_on, SimpleExecutor};
+
+    #[test]
+    fn join_handle_resolves_to_the_spawned_futures_output() {
+        let mut executor = SimpleExecutor::new();
+        let handle = executor.spawn(async { 42 });
+        executor.run();
+
+        assert_eq!(block_on(handle), 42);
+    }
 }
\ No newline at end of file
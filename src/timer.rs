@@ -0,0 +1,210 @@
+use crate::sync::SpinMutex;
+use alloc::{collections::BinaryHeap, sync::Arc};
+use core::{
+    cmp::Ordering,
+    future::Future,
+    pin::Pin,
+    sync::atomic::{AtomicU64, Ordering as AtomicOrdering},
+    task::{Context, Poll, Waker},
+};
+
+/// A monotonic tick source for the timer driver.
+///
+/// Implement this over whatever hardware counter or tick count the
+/// platform exposes (a PIT/APIC tick counter, a cycle counter, ...) so
+/// the timer driver stays `no_std` and independent of any particular
+/// time representation. Deadlines are expressed in the same units as
+/// `now()`.
+pub trait Clock: Send + Sync {
+    /// Returns the current time, in the same tick units used for deadlines.
+    fn now(&self) -> u64;
+}
+
+/// Generates the next unique ID used to identify a [`TimerEntry`] in the
+/// heap, so a dropped [`Timer`] can find and remove its own entry.
+fn next_timer_id() -> u64 {
+    static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+    NEXT_ID.fetch_add(1, AtomicOrdering::Relaxed)
+}
+
+/// A single pending timer: a deadline tick paired with the waker to
+/// notify once that deadline has passed.
+struct TimerEntry {
+    /// Uniquely identifies this entry so it can be removed before it fires
+    id: u64,
+    deadline: u64,
+    waker: Waker,
+}
+
+impl PartialEq for TimerEntry {
+    fn eq(&self, other: &Self) -> bool {
+        self.deadline == other.deadline
+    }
+}
+
+impl Eq for TimerEntry {}
+
+impl PartialOrd for TimerEntry {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for TimerEntry {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Reversed so `BinaryHeap` (a max-heap) pops the earliest deadline first.
+        other.deadline.cmp(&self.deadline)
+    }
+}
+
+/// A min-heap of pending timers ordered by deadline.
+///
+/// Modeled on Fuchsia's `TimerHeap`: timers register a `(deadline,
+/// waker)` pair and the driver wakes and pops everything whose deadline
+/// has passed on each executor tick.
+pub(crate) struct TimerHeap {
+    heap: BinaryHeap<TimerEntry>,
+}
+
+impl TimerHeap {
+    pub(crate) fn new() -> Self {
+        TimerHeap {
+            heap: BinaryHeap::new(),
+        }
+    }
+
+    /// Registers a waker to be woken once `deadline` has passed, returning
+    /// an ID that can later be passed to [`TimerHeap::remove`] to cancel it.
+    pub(crate) fn insert(&mut self, deadline: u64, waker: Waker) -> u64 {
+        let id = next_timer_id();
+        self.heap.push(TimerEntry { id, deadline, waker });
+        id
+    }
+
+    /// Removes a pending timer registered by [`TimerHeap::insert`], if it
+    /// hasn't already fired. Used by `Timer::drop` so a timer that's
+    /// dropped before its deadline doesn't linger in the heap forever.
+    pub(crate) fn remove(&mut self, id: u64) {
+        self.heap.retain(|entry| entry.id != id);
+    }
+
+    /// Returns the nearest pending deadline, if any.
+    pub(crate) fn next_deadline(&self) -> Option<u64> {
+        self.heap.peek().map(|entry| entry.deadline)
+    }
+
+    /// Wakes and removes every timer whose deadline is at or before `now`.
+    pub(crate) fn wake_expired(&mut self, now: u64) {
+        while let Some(entry) = self.heap.peek() {
+            if entry.deadline > now {
+                break;
+            }
+            let entry = self.heap.pop().expect("peeked entry must still be present");
+            entry.waker.wake();
+        }
+    }
+}
+
+/// Shared handle to an executor's timer heap, cloned into each [`Timer`]
+/// so it can register itself without borrowing the executor.
+pub(crate) type TimerHandle = Arc<SpinMutex<TimerHeap>>;
+
+/// A future that resolves once a given deadline has passed.
+///
+/// Returned by `Executor::sleep`. On first poll it registers its waker in
+/// the shared timer heap; on every poll (including the first) it re-checks
+/// the clock before reporting `Pending`, so a spurious early wakeup leaves
+/// its existing heap entry in place rather than resolving early. Later
+/// polls do not replace the waker registered on the first poll.
+///
+/// If dropped before its deadline, its entry is removed from the heap
+/// (see `Drop`) instead of lingering there until it would have fired.
+pub struct Timer {
+    deadline: u64,
+    /// ID of this timer's entry in `heap`, once registered
+    id: Option<u64>,
+    heap: TimerHandle,
+    clock: Arc<dyn Clock>,
+}
+
+impl Timer {
+    pub(crate) fn new(deadline: u64, heap: TimerHandle, clock: Arc<dyn Clock>) -> Self {
+        Timer {
+            deadline,
+            id: None,
+            heap,
+            clock,
+        }
+    }
+}
+
+impl Future for Timer {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<()> {
+        if self.clock.now() >= self.deadline {
+            return Poll::Ready(());
+        }
+        if self.id.is_none() {
+            let deadline = self.deadline;
+            let id = self.heap.with(|heap| heap.insert(deadline, cx.waker().clone()));
+            self.id = Some(id);
+        }
+        Poll::Pending
+    }
+}
+
+impl Drop for Timer {
+    fn drop(&mut self) {
+        if let Some(id) = self.id {
+            self.heap.with(|heap| heap.remove(id));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simple_executor::dummy_waker;
+
+    struct FixedClock(u64);
+
+    impl Clock for FixedClock {
+        fn now(&self) -> u64 {
+            self.0
+        }
+    }
+
+    #[test]
+    fn wake_expired_fires_in_deadline_order_and_leaves_later_ones_pending() {
+        let mut heap = TimerHeap::new();
+        let waker = dummy_waker();
+        heap.insert(30, waker.clone());
+        heap.insert(10, waker.clone());
+        heap.insert(20, waker);
+
+        assert_eq!(heap.next_deadline(), Some(10));
+
+        heap.wake_expired(20);
+        assert_eq!(heap.next_deadline(), Some(30));
+
+        heap.wake_expired(30);
+        assert_eq!(heap.next_deadline(), None);
+    }
+
+    #[test]
+    fn dropping_a_timer_before_its_deadline_removes_its_heap_entry() {
+        let heap: TimerHandle = Arc::new(SpinMutex::new(TimerHeap::new()));
+        let clock: Arc<dyn Clock> = Arc::new(FixedClock(0));
+        let waker = dummy_waker();
+
+        {
+            let mut timer = Timer::new(100, heap.clone(), clock);
+            let mut context = Context::from_waker(&waker);
+            assert_eq!(Pin::new(&mut timer).poll(&mut context), Poll::Pending);
+            assert_eq!(heap.with(|h| h.next_deadline()), Some(100));
+        } // `timer` dropped here, well before its deadline
+
+        assert_eq!(heap.with(|h| h.next_deadline()), None);
+    }
+}